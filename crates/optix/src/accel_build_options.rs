@@ -0,0 +1,77 @@
+use crate::{motion_transform::MotionOptions, sys};
+
+bitflags::bitflags! {
+    pub struct BuildFlags: u32 {
+        const NONE = sys::OptixBuildFlags_OPTIX_BUILD_FLAG_NONE;
+        const ALLOW_UPDATE = sys::OptixBuildFlags_OPTIX_BUILD_FLAG_ALLOW_UPDATE;
+        const ALLOW_COMPACTION = sys::OptixBuildFlags_OPTIX_BUILD_FLAG_ALLOW_COMPACTION;
+        const PREFER_FAST_TRACE = sys::OptixBuildFlags_OPTIX_BUILD_FLAG_PREFER_FAST_TRACE;
+        const PREFER_FAST_BUILD = sys::OptixBuildFlags_OPTIX_BUILD_FLAG_PREFER_FAST_BUILD;
+        const ALLOW_RANDOM_VERTEX_ACCESS = sys::OptixBuildFlags_OPTIX_BUILD_FLAG_ALLOW_RANDOM_VERTEX_ACCESS;
+    }
+}
+
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BuildOperation {
+    Build = sys::OptixBuildOperation_OPTIX_BUILD_OPERATION_BUILD,
+    Update = sys::OptixBuildOperation_OPTIX_BUILD_OPERATION_UPDATE,
+}
+
+/// The options passed alongside a [`BuildInput`][crate::acceleration::BuildInput]
+/// to `optixAccelComputeMemoryUsage`/`optixAccelBuild`.
+///
+/// To build a GAS/IAS whose instances reference a `MatrixMotionTransform`
+/// or `SrtMotionTransform`, the accel itself must be built with matching
+/// motion options via [`AccelBuildOptions::motion_options`] — OptiX
+/// requires the `numKeys`/`timeBegin`/`timeEnd` declared here to agree
+/// with the motion transform nodes the build references.
+#[derive(Debug, Copy, Clone)]
+pub struct AccelBuildOptions {
+    build_flags: BuildFlags,
+    operation: BuildOperation,
+    motion_options: sys::OptixMotionOptions,
+}
+
+impl AccelBuildOptions {
+    pub fn new(operation: BuildOperation) -> AccelBuildOptions {
+        AccelBuildOptions {
+            build_flags: BuildFlags::NONE,
+            operation,
+            motion_options: sys::OptixMotionOptions {
+                numKeys: 0,
+                flags: 0,
+                timeBegin: 0.0,
+                timeEnd: 0.0,
+            },
+        }
+    }
+
+    pub fn build_flags(mut self, build_flags: BuildFlags) -> AccelBuildOptions {
+        self.build_flags = build_flags;
+        self
+    }
+
+    /// Declares motion for this accel build: `numKeys` motion keys spanning
+    /// `[time_begin, time_end]`, with `flags` controlling out-of-range
+    /// behavior. Pass the same values used to construct the motion
+    /// transform node(s) this accel's instances reference.
+    pub fn motion_options(
+        mut self,
+        num_keys: u16,
+        time_begin: f32,
+        time_end: f32,
+        flags: crate::motion_transform::MotionFlags,
+    ) -> AccelBuildOptions {
+        self.motion_options = MotionOptions::new(num_keys, time_begin, time_end, flags).to_sys();
+        self
+    }
+
+    pub(crate) fn to_sys(self) -> sys::OptixAccelBuildOptions {
+        sys::OptixAccelBuildOptions {
+            buildFlags: self.build_flags.bits(),
+            operation: self.operation as sys::OptixBuildOperation,
+            motionOptions: self.motion_options,
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use crate::{
+    acceleration::{BuildInput, GeometryFlags},
+    module::Module,
+    sys,
+};
+use cust::memory::DeviceSlice;
+use cust_raw::CUdeviceptr;
+use mint::Vector3;
+
+/// The basis used to interpret a curve's control points.
+///
+/// Each variant maps directly to one of OptiX's built-in curve primitive
+/// types and therefore to a specific builtin intersection-program module
+/// (see [`Module::builtin_is_module_get`]).
+///
+/// Round-linear curves perform no backface culling, unlike the B-spline
+/// variants, which affects how hit shaders should interpret the
+/// intersection's geometric normal.
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CurveType {
+    RoundLinear = sys::OptixPrimitiveType_OPTIX_PRIMITIVE_TYPE_ROUND_LINEAR,
+    RoundQuadraticBSpline = sys::OptixPrimitiveType_OPTIX_PRIMITIVE_TYPE_ROUND_QUADRATIC_BSPLINE,
+    RoundCubicBSpline = sys::OptixPrimitiveType_OPTIX_PRIMITIVE_TYPE_ROUND_CUBIC_BSPLINE,
+}
+
+impl CurveType {
+    fn primitive_type_flags(self) -> u32 {
+        match self {
+            CurveType::RoundLinear => sys::OptixPrimitiveTypeFlags_OPTIX_PRIMITIVE_TYPE_FLAGS_ROUND_LINEAR,
+            CurveType::RoundQuadraticBSpline => {
+                sys::OptixPrimitiveTypeFlags_OPTIX_PRIMITIVE_TYPE_FLAGS_ROUND_QUADRATIC_BSPLINE
+            }
+            CurveType::RoundCubicBSpline => {
+                sys::OptixPrimitiveTypeFlags_OPTIX_PRIMITIVE_TYPE_FLAGS_ROUND_CUBIC_BSPLINE
+            }
+        }
+    }
+
+    fn to_sys(self) -> sys::OptixPrimitiveType {
+        self as sys::OptixPrimitiveType
+    }
+
+    /// ORs this curve type's bit into `usesPrimitiveTypeFlags` on a
+    /// pipeline's compile options.
+    ///
+    /// A pipeline that traces a `CurveArray` built with this `CurveType`
+    /// must call this when assembling its `OptixPipelineCompileOptions`,
+    /// or OptiX will reject the pipeline for tracing a primitive type it
+    /// wasn't told to expect.
+    pub fn enable_on(self, compile_options: &mut sys::OptixPipelineCompileOptions) {
+        compile_options.usesPrimitiveTypeFlags |= self.primitive_type_flags();
+    }
+}
+
+bitflags::bitflags! {
+    /// Controls whether a curve's endpoints are capped.
+    ///
+    /// Endcaps behave differently depending on [`CurveType`]: for linear
+    /// curves the default leaves the ends unbounded, while B-spline curves
+    /// need `ON` to close the tips of the tube.
+    pub struct CurveEndcapFlags: u32 {
+        const DEFAULT = sys::OptixCurveEndcapFlags_OPTIX_CURVE_ENDCAP_DEFAULT;
+        const ON = sys::OptixCurveEndcapFlags_OPTIX_CURVE_ENDCAP_ON;
+    }
+}
+
+/// A [`BuildInput`] over OptiX's built-in curve/hair primitive.
+///
+/// Curves are defined by a sequence of control points (one vertex buffer
+/// per motion key), a parallel width (radius) buffer, and an index buffer
+/// whose entries point at the first control point of each curve segment.
+pub struct CurveArray<'v> {
+    curve_type: CurveType,
+    vertex_buffers: &'v [&'v DeviceSlice<Vector3<f32>>],
+    vertex_buffer_ptrs: Vec<CUdeviceptr>,
+    vertex_stride_in_bytes: u32,
+    width_buffers: &'v [&'v DeviceSlice<f32>],
+    width_buffer_ptrs: Vec<CUdeviceptr>,
+    width_stride_in_bytes: u32,
+    index_buffer: &'v DeviceSlice<u32>,
+    index_stride_in_bytes: u32,
+    flags: GeometryFlags,
+    primitive_index_offset: u32,
+    endcap_flags: CurveEndcapFlags,
+}
+
+impl<'v> CurveArray<'v> {
+    pub fn new(
+        curve_type: CurveType,
+        vertex_buffers: &'v [&'v DeviceSlice<Vector3<f32>>],
+        width_buffers: &'v [&'v DeviceSlice<f32>],
+        index_buffer: &'v DeviceSlice<u32>,
+    ) -> CurveArray<'v> {
+        assert_eq!(
+            vertex_buffers.len(),
+            width_buffers.len(),
+            "CurveArray requires one width buffer per motion key"
+        );
+        // Resolve device pointers up front and hold onto them: `to_sys`
+        // hands the sys struct a raw `*const CUdeviceptr`, which would
+        // dangle if we built the pointer array fresh on every call instead.
+        let vertex_buffer_ptrs = vertex_buffers.iter().map(|b| b.as_device_ptr()).collect();
+        let width_buffer_ptrs = width_buffers.iter().map(|b| b.as_device_ptr()).collect();
+        CurveArray {
+            curve_type,
+            vertex_buffers,
+            vertex_buffer_ptrs,
+            vertex_stride_in_bytes: 0,
+            width_buffers,
+            width_buffer_ptrs,
+            width_stride_in_bytes: 0,
+            index_buffer,
+            index_stride_in_bytes: 0,
+            flags: GeometryFlags::NONE,
+            primitive_index_offset: 0,
+            endcap_flags: CurveEndcapFlags::DEFAULT,
+        }
+    }
+
+    pub fn vertex_stride_in_bytes(mut self, vertex_stride_in_bytes: u32) -> CurveArray<'v> {
+        self.vertex_stride_in_bytes = vertex_stride_in_bytes;
+        self
+    }
+
+    pub fn width_stride_in_bytes(mut self, width_stride_in_bytes: u32) -> CurveArray<'v> {
+        self.width_stride_in_bytes = width_stride_in_bytes;
+        self
+    }
+
+    pub fn index_stride_in_bytes(mut self, index_stride_in_bytes: u32) -> CurveArray<'v> {
+        self.index_stride_in_bytes = index_stride_in_bytes;
+        self
+    }
+
+    pub fn flags(mut self, flags: GeometryFlags) -> CurveArray<'v> {
+        self.flags = flags;
+        self
+    }
+
+    pub fn primitive_index_offset(mut self, primitive_index_offset: u32) -> CurveArray<'v> {
+        self.primitive_index_offset = primitive_index_offset;
+        self
+    }
+
+    pub fn endcap_flags(mut self, endcap_flags: CurveEndcapFlags) -> CurveArray<'v> {
+        self.endcap_flags = endcap_flags;
+        self
+    }
+
+    pub fn curve_type(&self) -> CurveType {
+        self.curve_type
+    }
+
+    fn num_primitives(&self) -> u32 {
+        self.index_buffer.len() as u32
+    }
+}
+
+impl<'v> BuildInput for CurveArray<'v> {
+    fn to_sys(&self) -> sys::OptixBuildInput {
+        sys::OptixBuildInput {
+            type_: sys::OptixBuildInputType_OPTIX_BUILD_INPUT_TYPE_CURVES,
+            input: sys::OptixBuildInputUnion {
+                curve_array: std::mem::ManuallyDrop::new(sys::OptixBuildInputCurveArray {
+                    curveType: self.curve_type.to_sys(),
+                    numPrimitives: self.num_primitives(),
+                    vertexBuffers: self.vertex_buffer_ptrs.as_ptr(),
+                    numVertices: self
+                        .vertex_buffers
+                        .first()
+                        .map(|b| b.len() as u32)
+                        .unwrap_or(0),
+                    vertexStrideInBytes: self.vertex_stride_in_bytes,
+                    widthBuffers: self.width_buffer_ptrs.as_ptr(),
+                    widthStrideInBytes: self.width_stride_in_bytes,
+                    normalBuffers: std::ptr::null(),
+                    normalStrideInBytes: 0,
+                    indexBuffer: self.index_buffer.as_device_ptr(),
+                    indexStrideInBytes: self.index_stride_in_bytes,
+                    flag: self.flags.bits(),
+                    primitiveIndexOffset: self.primitive_index_offset,
+                    endcapFlags: self.endcap_flags.bits(),
+                }),
+            },
+        }
+    }
+}
+
+impl Module {
+    /// Fetches the builtin intersection-program module for a curve type,
+    /// required because built-in curve primitives cannot use a user-supplied
+    /// intersection shader.
+    ///
+    /// This wraps `optixBuiltinISModuleGet`; the returned module must be
+    /// referenced by the hit-group program group used to shade the curves.
+    pub fn builtin_curve_is_module(
+        &self,
+        curve_type: CurveType,
+        endcap_flags: CurveEndcapFlags,
+    ) -> Result<Module, crate::error::Error> {
+        self.builtin_is_module_get(sys::OptixBuiltinISOptions {
+            builtinISModuleType: curve_type.to_sys(),
+            usesMotionBlur: 0,
+            buildFlags: 0,
+            curveEndcapFlags: endcap_flags.bits(),
+        })
+    }
+}
@@ -5,6 +5,31 @@ use cust::{memory::DeviceSlice, DeviceCopy};
 use cust_raw::CUdeviceptr;
 use mint::RowMatrix3x4;
 
+/// Anything that resolves to a [`TraversableHandle`] and can therefore be
+/// the child of an [`Instance`] — an [`Accel`], or a motion transform such
+/// as `MatrixMotionTransform`/`SrtMotionTransform`.
+pub trait Traversable {
+    fn handle(&self) -> TraversableHandle;
+}
+
+impl Traversable for Accel {
+    fn handle(&self) -> TraversableHandle {
+        Accel::handle(self)
+    }
+}
+
+impl Traversable for crate::motion_transform::MatrixMotionTransform {
+    fn handle(&self) -> TraversableHandle {
+        crate::motion_transform::MatrixMotionTransform::handle(self)
+    }
+}
+
+impl Traversable for crate::motion_transform::SrtMotionTransform {
+    fn handle(&self) -> TraversableHandle {
+        crate::motion_transform::SrtMotionTransform::handle(self)
+    }
+}
+
 #[repr(C, align(16))]
 #[derive(Debug, Copy, Clone, DeviceCopy)]
 pub struct Instance<'a> {
@@ -35,18 +60,18 @@ bitflags::bitflags! {
 }
 
 impl<'a> Instance<'a> {
-    pub fn new(accel: &'a Accel) -> Instance<'a> {
+    pub fn new<T: Traversable>(traversable: &'a T) -> Instance<'a> {
         #[cfg_attr(rustfmt, rustfmt_skip)]
         Instance {
             transform: [
-                1.0, 0.0, 0.0, 0.0, 
-                0.0, 1.0, 0.0, 0.0, 
+                1.0, 0.0, 0.0, 0.0,
+                0.0, 1.0, 0.0, 0.0,
                 0.0, 0.0, 1.0, 0.0].into(),
             instance_id: 0,
             sbt_offset: 0,
             visibility_mask: 255,
             flags: InstanceFlags::NONE,
-            traversable_handle: accel.handle(),
+            traversable_handle: traversable.handle(),
             pad: [0; 2],
             accel: PhantomData,
         }
@@ -80,11 +105,35 @@ impl<'a> Instance<'a> {
 
 pub struct InstanceArray<'i, 'a> {
     instances: &'i DeviceSlice<Instance<'a>>,
+    #[cfg(not(any(feature = "optix72", feature = "optix73")))]
+    aabbs: Option<&'i DeviceSlice<f32>>,
 }
 
 impl<'i, 'a> InstanceArray<'i, 'a> {
     pub fn new(instances: &'i DeviceSlice<Instance<'a>>) -> InstanceArray<'i, 'a> {
-        InstanceArray { instances }
+        InstanceArray {
+            instances,
+            #[cfg(not(any(feature = "optix72", feature = "optix73")))]
+            aabbs: None,
+        }
+    }
+
+    /// Supplies precomputed, per-instance AABBs (6 `f32`s per instance —
+    /// min xyz then max xyz — one set per motion step) for instances whose
+    /// child traversable OptiX cannot bound automatically, such as a motion
+    /// transform or a custom-primitive GAS.
+    ///
+    /// Only meaningful on `optix70`/`optix71`; newer SDKs compute these
+    /// bounds themselves and dropped the fields from the build input.
+    #[cfg(not(any(feature = "optix72", feature = "optix73")))]
+    pub fn aabbs(mut self, aabbs: &'i DeviceSlice<f32>) -> InstanceArray<'i, 'a> {
+        assert_eq!(
+            aabbs.len() % 6,
+            0,
+            "aabbs must hold 6 floats (min xyz, max xyz) per instance per motion step"
+        );
+        self.aabbs = Some(aabbs);
+        self
     }
 }
 
@@ -102,14 +151,18 @@ impl<'i, 'a> BuildInput for InstanceArray<'i, 'a> {
                     }
                 }
             } else {
+                let (aabbs, num_aabbs) = match self.aabbs {
+                    Some(aabbs) => (aabbs.as_device_ptr(), (aabbs.len() / 6) as u32),
+                    None => (0, 0),
+                };
                 sys::OptixBuildInput {
                     type_: sys::OptixBuildInputType_OPTIX_BUILD_INPUT_TYPE_INSTANCES,
                     input: sys::OptixBuildInputUnion {
                         instance_array: std::mem::ManuallyDrop::new(sys::OptixBuildInputInstanceArray {
                             instances: self.instances.as_device_ptr(),
                             numInstances: self.instances.len() as u32,
-                            aabbs: 0,
-                            numAabbs: 0,
+                            aabbs,
+                            numAabbs: num_aabbs,
                         })
                     }
                 }
@@ -0,0 +1,220 @@
+use crate::{acceleration::TraversableHandle, const_assert_eq, error::Error, sys};
+use cust::memory::DeviceBuffer;
+use mint::RowMatrix3x4;
+
+bitflags::bitflags! {
+    /// Controls what happens to a ray's time value outside of
+    /// `[time_begin, time_end]`.
+    pub struct MotionFlags: u16 {
+        const NONE = sys::OptixMotionFlags_OPTIX_MOTION_FLAG_NONE as u16;
+        /// Vanish for times before `time_begin` instead of clamping to the
+        /// state at `time_begin`.
+        const START_VANISH = sys::OptixMotionFlags_OPTIX_MOTION_FLAG_START_VANISH as u16;
+        /// Vanish for times after `time_end` instead of clamping to the
+        /// state at `time_end`.
+        const END_VANISH = sys::OptixMotionFlags_OPTIX_MOTION_FLAG_END_VANISH as u16;
+    }
+}
+
+/// The `[time_begin, time_end]` interval and flags shared by all OptiX
+/// motion constructs (motion transforms and motion-enabled accels).
+#[derive(Debug, Copy, Clone)]
+pub struct MotionOptions {
+    pub num_keys: u16,
+    pub time_begin: f32,
+    pub time_end: f32,
+    pub flags: MotionFlags,
+}
+
+impl MotionOptions {
+    pub fn new(num_keys: u16, time_begin: f32, time_end: f32, flags: MotionFlags) -> MotionOptions {
+        assert!(num_keys >= 2, "a motion transform requires at least 2 motion keys");
+        MotionOptions {
+            num_keys,
+            time_begin,
+            time_end,
+            flags,
+        }
+    }
+
+    pub(crate) fn to_sys(self) -> sys::OptixMotionOptions {
+        sys::OptixMotionOptions {
+            numKeys: self.num_keys,
+            flags: self.flags.bits(),
+            timeBegin: self.time_begin,
+            timeEnd: self.time_end,
+        }
+    }
+}
+
+/// Returns the byte offset of a field within `T`, without requiring a live,
+/// initialized value of `T`.
+///
+/// `OptixMatrixMotionTransform`/`OptixSRTMotionTransform` are C
+/// flexible-array-member structs: the sys bindings declare their trailing
+/// key array at its minimum size (2 keys), but the real allocation backing
+/// them must be over-sized to hold `num_keys` keys. We lay those bytes out
+/// by hand below, so we need the real field offsets rather than the
+/// fixed-size struct's own `size_of`.
+unsafe fn field_offset<T, F>(project: impl FnOnce(*const T) -> *const F) -> usize {
+    let uninit = std::mem::MaybeUninit::<T>::uninit();
+    let base = uninit.as_ptr();
+    let field = project(base);
+    field as usize - base as usize
+}
+
+/// A traversable node that linearly interpolates between N >= 2 matrix
+/// keys and exposes the result as a [`TraversableHandle`].
+///
+/// Insert a `MatrixMotionTransform` between an [`Instance`][crate::Instance]
+/// and the child traversable it animates to get object/camera motion blur.
+pub struct MatrixMotionTransform {
+    #[allow(dead_code)]
+    buf: DeviceBuffer<u8>,
+    handle: TraversableHandle,
+}
+
+impl MatrixMotionTransform {
+    const KEY_FLOATS: usize = 12;
+
+    /// Allocates and fills an `OptixMatrixMotionTransform` on the device —
+    /// over-allocated to hold all `motion_options.num_keys` keys, since the
+    /// type's trailing `transform` array is a flexible array member — then
+    /// converts the resulting pointer into a traversable handle via
+    /// `optixConvertPointerToTraversableHandle`.
+    pub fn new(
+        child: TraversableHandle,
+        motion_options: MotionOptions,
+        keys: &[RowMatrix3x4<f32>],
+    ) -> Result<MatrixMotionTransform, Error> {
+        assert_eq!(
+            keys.len(),
+            motion_options.num_keys as usize,
+            "number of matrix keys must match motion_options.num_keys"
+        );
+
+        let child_offset =
+            unsafe { field_offset::<sys::OptixMatrixMotionTransform, _>(|p| std::ptr::addr_of!((*p).child)) };
+        let motion_options_offset = unsafe {
+            field_offset::<sys::OptixMatrixMotionTransform, _>(|p| std::ptr::addr_of!((*p).motionOptions))
+        };
+        let transform_offset = unsafe {
+            field_offset::<sys::OptixMatrixMotionTransform, _>(|p| std::ptr::addr_of!((*p).transform))
+        };
+
+        let key_bytes = Self::KEY_FLOATS * std::mem::size_of::<f32>();
+        let mut bytes = vec![0u8; transform_offset + keys.len() * key_bytes];
+
+        unsafe {
+            let base = bytes.as_mut_ptr();
+            base.add(child_offset)
+                .cast::<TraversableHandle>()
+                .write_unaligned(child);
+            base.add(motion_options_offset)
+                .cast::<sys::OptixMotionOptions>()
+                .write_unaligned(motion_options.to_sys());
+
+            let transform_ptr = base.add(transform_offset).cast::<[f32; Self::KEY_FLOATS]>();
+            for (i, key) in keys.iter().enumerate() {
+                let key: [f32; Self::KEY_FLOATS] = (*key).into();
+                transform_ptr.add(i).write_unaligned(key);
+            }
+        }
+
+        let buf = unsafe { DeviceBuffer::from_slice(&bytes)? };
+        let handle = unsafe { crate::acceleration::convert_pointer_to_traversable_handle(buf.as_device_ptr())? };
+
+        Ok(MatrixMotionTransform { buf, handle })
+    }
+
+    pub fn handle(&self) -> TraversableHandle {
+        self.handle
+    }
+}
+
+/// A single SRT (scale/rotation/translation) key as laid out by OptiX:
+/// `sx, a, b, pvx, sy, c, pvy, sz, pvz, qx, qy, qz, qw, tx, ty, tz`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct SrtData {
+    pub sx: f32,
+    pub a: f32,
+    pub b: f32,
+    pub pvx: f32,
+    pub sy: f32,
+    pub c: f32,
+    pub pvy: f32,
+    pub sz: f32,
+    pub pvz: f32,
+    pub qx: f32,
+    pub qy: f32,
+    pub qz: f32,
+    pub qw: f32,
+    pub tx: f32,
+    pub ty: f32,
+    pub tz: f32,
+}
+
+const_assert_eq!(std::mem::size_of::<SrtData>(), std::mem::size_of::<sys::OptixSRTData>());
+
+/// A traversable node that interpolates between N >= 2 SRT keys, which
+/// (unlike [`MatrixMotionTransform`]) can represent motion that includes
+/// rotation without shearing artifacts.
+pub struct SrtMotionTransform {
+    #[allow(dead_code)]
+    buf: DeviceBuffer<u8>,
+    handle: TraversableHandle,
+}
+
+impl SrtMotionTransform {
+    /// Allocates and fills an `OptixSRTMotionTransform` on the device —
+    /// over-allocated to hold all `motion_options.num_keys` keys, since the
+    /// type's trailing `srtData` array is a flexible array member — then
+    /// converts the resulting pointer into a traversable handle via
+    /// `optixConvertPointerToTraversableHandle`.
+    pub fn new(
+        child: TraversableHandle,
+        motion_options: MotionOptions,
+        keys: &[SrtData],
+    ) -> Result<SrtMotionTransform, Error> {
+        assert_eq!(
+            keys.len(),
+            motion_options.num_keys as usize,
+            "number of SRT keys must match motion_options.num_keys"
+        );
+
+        let child_offset =
+            unsafe { field_offset::<sys::OptixSRTMotionTransform, _>(|p| std::ptr::addr_of!((*p).child)) };
+        let motion_options_offset =
+            unsafe { field_offset::<sys::OptixSRTMotionTransform, _>(|p| std::ptr::addr_of!((*p).motionOptions)) };
+        let srt_data_offset =
+            unsafe { field_offset::<sys::OptixSRTMotionTransform, _>(|p| std::ptr::addr_of!((*p).srtData)) };
+
+        let key_bytes = std::mem::size_of::<SrtData>();
+        let mut bytes = vec![0u8; srt_data_offset + keys.len() * key_bytes];
+
+        unsafe {
+            let base = bytes.as_mut_ptr();
+            base.add(child_offset)
+                .cast::<TraversableHandle>()
+                .write_unaligned(child);
+            base.add(motion_options_offset)
+                .cast::<sys::OptixMotionOptions>()
+                .write_unaligned(motion_options.to_sys());
+
+            let srt_data_ptr = base.add(srt_data_offset).cast::<SrtData>();
+            for (i, key) in keys.iter().enumerate() {
+                srt_data_ptr.add(i).write_unaligned(*key);
+            }
+        }
+
+        let buf = unsafe { DeviceBuffer::from_slice(&bytes)? };
+        let handle = unsafe { crate::acceleration::convert_pointer_to_traversable_handle(buf.as_device_ptr())? };
+
+        Ok(SrtMotionTransform { buf, handle })
+    }
+
+    pub fn handle(&self) -> TraversableHandle {
+        self.handle
+    }
+}
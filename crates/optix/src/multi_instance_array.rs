@@ -0,0 +1,64 @@
+use crate::{
+    acceleration::Accel,
+    error::Error,
+    instance_array::{Instance, InstanceArray},
+};
+use cust::memory::DeviceBuffer;
+use mint::RowMatrix3x4;
+
+/// One sub-accel to be stitched into a [`MultiInstanceArray`]: the
+/// traversable it builds (typically resident on, or built for, a single
+/// stream/device/context), the transform it is instanced under, and the
+/// number of SBT records its geometry needs.
+pub struct InstanceGroup<'a> {
+    pub accel: &'a Accel,
+    pub transform: RowMatrix3x4<f32>,
+    pub num_sbt_records: u32,
+}
+
+impl<'a> InstanceGroup<'a> {
+    pub fn new(accel: &'a Accel, transform: RowMatrix3x4<f32>, num_sbt_records: u32) -> InstanceGroup<'a> {
+        InstanceGroup {
+            accel,
+            transform,
+            num_sbt_records,
+        }
+    }
+}
+
+/// Assembles one [`Instance`] per [`InstanceGroup`] into a single
+/// `DeviceBuffer`, assigning each group a non-overlapping `sbt_offset`
+/// range so its hit records land in the right span of the top-level SBT.
+///
+/// This is the Rust-CUDA analogue of stitching several independently
+/// built per-device sub-BVHs into one multi-BVH instance array: callers no
+/// longer have to track SBT offset arithmetic or instance buffer lifetimes
+/// by hand.
+pub struct MultiInstanceArray<'a> {
+    instances: DeviceBuffer<Instance<'a>>,
+}
+
+impl<'a> MultiInstanceArray<'a> {
+    pub fn build(groups: &[InstanceGroup<'a>]) -> Result<MultiInstanceArray<'a>, Error> {
+        let mut host_instances = Vec::with_capacity(groups.len());
+        let mut sbt_offset = 0u32;
+        for (instance_id, group) in groups.iter().enumerate() {
+            host_instances.push(
+                Instance::new(group.accel)
+                    .instance_id(instance_id as u32)
+                    .sbt_offset(sbt_offset)
+                    .transform(group.transform),
+            );
+            sbt_offset += group.num_sbt_records;
+        }
+
+        let instances = unsafe { DeviceBuffer::from_slice(&host_instances)? };
+        Ok(MultiInstanceArray { instances })
+    }
+
+    /// Borrows the combined instance buffer as an [`InstanceArray`] ready
+    /// to be fed into the top-level IAS build.
+    pub fn as_instance_array(&self) -> InstanceArray<'_, 'a> {
+        InstanceArray::new(&self.instances)
+    }
+}
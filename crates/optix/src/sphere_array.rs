@@ -0,0 +1,154 @@
+#![cfg(feature = "optix74")]
+
+use crate::{
+    acceleration::{BuildInput, GeometryFlags},
+    module::Module,
+    sys,
+};
+use cust::memory::DeviceSlice;
+use cust_raw::CUdeviceptr;
+use mint::Vector3;
+
+/// A [`BuildInput`] over OptiX's built-in sphere primitive, used for point
+/// clouds and particle data where every primitive is a single sphere
+/// rather than a triangle mesh.
+///
+/// Only available on `optix74` and newer, where
+/// `OPTIX_BUILD_INPUT_TYPE_SPHERES` was introduced.
+pub struct SphereArray<'v> {
+    vertex_buffers: &'v [&'v DeviceSlice<Vector3<f32>>],
+    vertex_buffer_ptrs: Vec<CUdeviceptr>,
+    vertex_stride_in_bytes: u32,
+    radius_buffers: &'v [&'v DeviceSlice<f32>],
+    radius_buffer_ptrs: Vec<CUdeviceptr>,
+    radius_stride_in_bytes: u32,
+    single_radius: bool,
+    index_buffer: Option<&'v DeviceSlice<u32>>,
+    // One geometry-flags word per SBT record; we always build with a single
+    // SBT record, but `OptixBuildInputSphereArray::flags` still takes a
+    // pointer, so it needs owned, struct-lifetime-backed storage.
+    sbt_flags: [u32; 1],
+    primitive_index_offset: u32,
+}
+
+impl<'v> SphereArray<'v> {
+    pub fn new(
+        vertex_buffers: &'v [&'v DeviceSlice<Vector3<f32>>],
+        radius_buffers: &'v [&'v DeviceSlice<f32>],
+    ) -> SphereArray<'v> {
+        assert_eq!(
+            vertex_buffers.len(),
+            radius_buffers.len(),
+            "SphereArray requires one radius buffer per motion key"
+        );
+        // Resolve device pointers up front and hold onto them: `to_sys`
+        // hands the sys struct a raw `*const CUdeviceptr`, which would
+        // dangle if we built the pointer array fresh on every call instead.
+        let vertex_buffer_ptrs = vertex_buffers.iter().map(|b| b.as_device_ptr()).collect();
+        let radius_buffer_ptrs = radius_buffers.iter().map(|b| b.as_device_ptr()).collect();
+        SphereArray {
+            vertex_buffers,
+            vertex_buffer_ptrs,
+            vertex_stride_in_bytes: 0,
+            radius_buffers,
+            radius_buffer_ptrs,
+            radius_stride_in_bytes: 0,
+            single_radius: false,
+            index_buffer: None,
+            sbt_flags: [GeometryFlags::NONE.bits()],
+            primitive_index_offset: 0,
+        }
+    }
+
+    pub fn vertex_stride_in_bytes(mut self, vertex_stride_in_bytes: u32) -> SphereArray<'v> {
+        self.vertex_stride_in_bytes = vertex_stride_in_bytes;
+        self
+    }
+
+    pub fn radius_stride_in_bytes(mut self, radius_stride_in_bytes: u32) -> SphereArray<'v> {
+        self.radius_stride_in_bytes = radius_stride_in_bytes;
+        self
+    }
+
+    /// When set, each radius buffer must contain a single element which is
+    /// applied to every sphere, instead of one radius per vertex.
+    pub fn single_radius(mut self, single_radius: bool) -> SphereArray<'v> {
+        self.single_radius = single_radius;
+        self
+    }
+
+    pub fn index_buffer(mut self, index_buffer: &'v DeviceSlice<u32>) -> SphereArray<'v> {
+        self.index_buffer = Some(index_buffer);
+        self
+    }
+
+    pub fn flags(mut self, flags: GeometryFlags) -> SphereArray<'v> {
+        self.sbt_flags = [flags.bits()];
+        self
+    }
+
+    pub fn primitive_index_offset(mut self, primitive_index_offset: u32) -> SphereArray<'v> {
+        self.primitive_index_offset = primitive_index_offset;
+        self
+    }
+
+}
+
+impl<'v> BuildInput for SphereArray<'v> {
+    fn to_sys(&self) -> sys::OptixBuildInput {
+        sys::OptixBuildInput {
+            type_: sys::OptixBuildInputType_OPTIX_BUILD_INPUT_TYPE_SPHERES,
+            input: sys::OptixBuildInputUnion {
+                sphere_array: std::mem::ManuallyDrop::new(sys::OptixBuildInputSphereArray {
+                    vertexBuffers: self.vertex_buffer_ptrs.as_ptr(),
+                    vertexStrideInBytes: self.vertex_stride_in_bytes,
+                    numVertices: self
+                        .vertex_buffers
+                        .first()
+                        .map(|b| b.len() as u32)
+                        .unwrap_or(0),
+                    radiusBuffers: self.radius_buffer_ptrs.as_ptr(),
+                    radiusStrideInBytes: self.radius_stride_in_bytes,
+                    singleRadius: self.single_radius as i32,
+                    indexBuffer: self
+                        .index_buffer
+                        .map(|b| b.as_device_ptr())
+                        .unwrap_or(0),
+                    indexStrideInBytes: 0,
+                    numSbtRecords: 1,
+                    sbtIndexOffsetBuffer: 0,
+                    sbtIndexOffsetSizeInBytes: 0,
+                    sbtIndexOffsetStrideInBytes: 0,
+                    primitiveIndexOffset: self.primitive_index_offset,
+                    flags: self.sbt_flags.as_ptr(),
+                }),
+            },
+        }
+    }
+}
+
+impl Module {
+    /// Fetches the builtin intersection-program module for the sphere
+    /// primitive (`OPTIX_PRIMITIVE_TYPE_SPHERE`), required because built-in
+    /// sphere primitives cannot use a user-supplied intersection shader.
+    pub fn builtin_sphere_is_module(&self) -> Result<Module, crate::error::Error> {
+        self.builtin_is_module_get(sys::OptixBuiltinISOptions {
+            builtinISModuleType: sys::OptixPrimitiveType_OPTIX_PRIMITIVE_TYPE_SPHERE,
+            usesMotionBlur: 0,
+            buildFlags: 0,
+            curveEndcapFlags: 0,
+        })
+    }
+}
+
+impl<'v> SphereArray<'v> {
+    /// ORs the sphere primitive's bit into `usesPrimitiveTypeFlags` on a
+    /// pipeline's compile options.
+    ///
+    /// A pipeline that traces a `SphereArray` must call this when
+    /// assembling its `OptixPipelineCompileOptions`, or OptiX will reject
+    /// the pipeline for tracing a primitive type it wasn't told to expect.
+    pub fn enable_on(compile_options: &mut sys::OptixPipelineCompileOptions) {
+        compile_options.usesPrimitiveTypeFlags |= sys::OptixPrimitiveTypeFlags_OPTIX_PRIMITIVE_TYPE_FLAGS_SPHERE;
+    }
+}